@@ -0,0 +1,142 @@
+//! Subscription to WPD device change-events.
+//!
+//! [`Device::events`] registers an `IPortableDeviceEventCallback` with the device and forwards each
+//! notification, decoded into a [`DeviceEvent`], over an [`mpsc::Receiver`]. This follows the
+//! watcher-thread-plus-channel design of editor VFS layers and lets callers build incremental sync
+//! without the cache that MTP otherwise forbids. The callback is unregistered when the returned
+//! [`EventStream`] is dropped.
+
+use std::sync::mpsc::{self, Receiver, Sender, RecvError, TryRecvError};
+
+use windows::core::{implement, PCWSTR};
+use windows::Win32::Devices::PortableDevices::{
+    IPortableDevice, IPortableDeviceEventCallback, IPortableDeviceEventCallback_Impl,
+    IPortableDeviceValues,
+    WPD_EVENT_PARAMETER_EVENT_ID, WPD_OBJECT_ID,
+    WPD_EVENT_OBJECT_ADDED, WPD_EVENT_OBJECT_REMOVED, WPD_EVENT_OBJECT_UPDATED,
+    WPD_EVENT_DEVICE_CAPABILITIES_UPDATED, WPD_EVENT_STORAGE_FORMAT,
+};
+use widestring::U16CString;
+
+use super::Device;
+
+/// A decoded device change-notification.
+///
+/// The object-scoped variants carry the affected MTP id so the caller can resolve it through
+/// [`Content::object_by_id`](super::Content::object_by_id).
+#[derive(Debug, Clone)]
+pub enum DeviceEvent {
+    /// An object was created on the device.
+    ObjectAdded { id: U16CString },
+    /// An object was removed from the device.
+    ObjectRemoved { id: U16CString },
+    /// An existing object's properties or contents changed.
+    ObjectUpdated { id: U16CString },
+    /// The device's capabilities changed; cached capability queries are stale.
+    DeviceCapabilitiesChanged,
+    /// A storage was (re)formatted.
+    StorageFormat,
+}
+
+impl DeviceEvent {
+    /// Decode a WPD event-parameters collection, returning `None` for events we do not model.
+    fn from_parameters(parameters: &IPortableDeviceValues) -> Option<Self> {
+        let event_id = unsafe { parameters.GetGuidValue(&WPD_EVENT_PARAMETER_EVENT_ID as *const _) }.ok()?;
+
+        let object_id = || unsafe {
+            parameters.GetStringValue(&WPD_OBJECT_ID as *const _).ok()
+                .map(|pwstr| U16CString::from_vec_truncate(pwstr.as_wide()))
+                .unwrap_or_default()
+        };
+
+        let event = if event_id == WPD_EVENT_OBJECT_ADDED {
+            DeviceEvent::ObjectAdded { id: object_id() }
+        } else if event_id == WPD_EVENT_OBJECT_REMOVED {
+            DeviceEvent::ObjectRemoved { id: object_id() }
+        } else if event_id == WPD_EVENT_OBJECT_UPDATED {
+            DeviceEvent::ObjectUpdated { id: object_id() }
+        } else if event_id == WPD_EVENT_DEVICE_CAPABILITIES_UPDATED {
+            DeviceEvent::DeviceCapabilitiesChanged
+        } else if event_id == WPD_EVENT_STORAGE_FORMAT {
+            DeviceEvent::StorageFormat
+        } else {
+            return None;
+        };
+        Some(event)
+    }
+}
+
+/// The COM callback WPD invokes on every event; it just decodes and forwards onto the channel.
+#[implement(IPortableDeviceEventCallback)]
+struct EventCallback {
+    sender: Sender<DeviceEvent>,
+}
+
+impl IPortableDeviceEventCallback_Impl for EventCallback_Impl {
+    fn OnEvent(&self, event_parameters: windows::core::Ref<IPortableDeviceValues>) -> windows::core::Result<()> {
+        if let Some(parameters) = event_parameters.as_ref() {
+            if let Some(event) = DeviceEvent::from_parameters(parameters) {
+                // The receiver has hung up only if the caller dropped the stream; ignore the error.
+                let _ = self.sender.send(event);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A live subscription to a device's change-events.
+///
+/// Decoded [`DeviceEvent`]s arrive on the wrapped channel; dropping the stream calls `Unadvise` to
+/// unregister the callback.
+pub struct EventStream {
+    device: IPortableDevice,
+    cookie: U16CString,
+    receiver: Receiver<DeviceEvent>,
+}
+
+impl EventStream {
+    /// Block until the next event arrives.
+    pub fn recv(&self) -> Result<DeviceEvent, RecvError> {
+        self.receiver.recv()
+    }
+
+    /// Return the next event if one is already queued, without blocking.
+    pub fn try_recv(&self) -> Result<DeviceEvent, TryRecvError> {
+        self.receiver.try_recv()
+    }
+
+    /// Iterate events as they arrive, blocking between them, until the stream is dropped.
+    pub fn iter(&self) -> std::sync::mpsc::Iter<'_, DeviceEvent> {
+        self.receiver.iter()
+    }
+}
+
+impl Drop for EventStream {
+    fn drop(&mut self) {
+        // Best-effort unregistration; nothing useful to do if the device is already gone.
+        let _ = unsafe { self.device.Unadvise(PCWSTR::from_raw(self.cookie.as_ptr())) };
+    }
+}
+
+impl Device {
+    /// Subscribe to the device's change-events.
+    ///
+    /// Registers an event callback and returns an [`EventStream`] whose channel yields a decoded
+    /// [`DeviceEvent`] per notification. The callback is unregistered when the stream is dropped.
+    pub fn events(&self) -> crate::WindowsResult<EventStream> {
+        let (sender, receiver) = mpsc::channel();
+        let callback: IPortableDeviceEventCallback = EventCallback { sender }.into();
+
+        let cookie_pwstr = unsafe { self.com_device().Advise(0, &callback, None) }?;
+        let cookie = U16CString::from_vec_truncate(unsafe { cookie_pwstr.as_wide() });
+        // `Advise` hands ownership of the cookie string to us; release the COM allocation now that
+        // it is copied into `cookie`.
+        unsafe { windows::Win32::System::Com::CoTaskMemFree(Some(cookie_pwstr.as_ptr() as *const _)) };
+
+        Ok(EventStream {
+            device: self.com_device().clone(),
+            cookie,
+            receiver,
+        })
+    }
+}
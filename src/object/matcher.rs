@@ -0,0 +1,295 @@
+//! Glob-style pattern matching over MTP object paths.
+//!
+//! A [`Matcher`] compiles a relative glob (e.g. `DCIM/**/*.jpg`) into one matcher per path
+//! component and drives a small staged NFA over the tree: each directory level holds a set of
+//! "active" states, a literal or single-level `*` advances one state, and `**` keeps its own state
+//! alive across levels so it can span any number of components. The compiled matcher is reusable,
+//! so the same pattern can be run against several devices.
+
+use std::borrow::Borrow;
+
+use super::{Object, ObjectType, ObjectIterator};
+
+/// A compiled relative glob pattern.
+///
+/// Build one with [`Matcher::new`] and run it against a subtree with [`Matcher::matches_in`], or
+/// go through the convenience wrapper [`Object::object_by_pattern`](super::Object::object_by_pattern).
+/// Matching ignores ASCII case, because MTP object names commonly differ only by case; non-ASCII
+/// characters are compared exactly.
+#[derive(Debug, Clone)]
+pub struct Matcher {
+    segments: Vec<Segment>,
+}
+
+/// One `/`-delimited component of a compiled pattern.
+#[derive(Debug, Clone)]
+enum Segment {
+    /// `**` — matches any number of path components, including zero.
+    Recursive,
+    /// A single-component pattern made of literals, `?`, `*` and character classes.
+    Pattern(Vec<Token>),
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    /// `?` — exactly one character.
+    One,
+    /// `*` — any run of characters within a single name component.
+    Any,
+    /// A literal (already lowercased for case-insensitive comparison).
+    Literal(char),
+    /// `[abc]` / `[a-z]` — one character from the class.
+    Class(Vec<ClassItem>),
+}
+
+#[derive(Debug, Clone)]
+enum ClassItem {
+    Char(char),
+    Range(char, char),
+}
+
+/// Error raised while compiling a [`Matcher`] from a pattern string.
+#[derive(Debug, thiserror::Error)]
+pub enum PatternError {
+    #[error("character class is never closed with ']'")]
+    UnterminatedClass,
+    #[error("empty character class '[]'")]
+    EmptyClass,
+    #[error("the pattern is empty")]
+    EmptyPattern,
+}
+
+impl Matcher {
+    /// Compile a relative glob such as `DCIM/**/*.jpg`.
+    ///
+    /// Supported metacharacters are `?` (one character), `*` (any run within a single component),
+    /// `**` (spanning any number of components) and `[..]` character classes.
+    pub fn new(pattern: &str) -> Result<Self, PatternError> {
+        let pattern = pattern.trim_matches('/');
+        if pattern.is_empty() {
+            return Err(PatternError::EmptyPattern);
+        }
+
+        let mut segments = Vec::new();
+        for component in pattern.split('/') {
+            if component == "**" {
+                segments.push(Segment::Recursive);
+            } else {
+                segments.push(Segment::Pattern(compile_component(component)?));
+            }
+        }
+        Ok(Self { segments })
+    }
+
+    /// Iterate every non-folder object beneath `root` whose relative path matches this pattern.
+    ///
+    /// The returned iterator borrows the compiled matcher, so the same `Matcher` can be run against
+    /// several devices without recompiling the glob.
+    pub fn matches_in<'a>(&'a self, root: &Object) -> PatternMatches<&'a Matcher> {
+        PatternMatches::start(self, root)
+    }
+
+    /// Like [`matches_in`](Self::matches_in) but consuming the matcher, so the iterator owns it and
+    /// can be returned from a function without a borrow. Used by
+    /// [`Object::object_by_pattern`](super::Object::object_by_pattern).
+    pub(super) fn into_matches_in(self, root: &Object) -> PatternMatches<Matcher> {
+        PatternMatches::start(self, root)
+    }
+
+    /// Expand a set of NFA states with the epsilon moves that let `**` match zero components.
+    fn closure(&self, states: Vec<usize>) -> Vec<usize> {
+        let mut out = Vec::new();
+        let mut stack = states;
+        while let Some(s) = stack.pop() {
+            if out.contains(&s) {
+                continue;
+            }
+            out.push(s);
+            if let Some(Segment::Recursive) = self.segments.get(s) {
+                // `**` may match zero components, so the following state is reachable too.
+                stack.push(s + 1);
+            }
+        }
+        out
+    }
+
+    /// Transition the active state set on encountering a component named `name`.
+    fn advance(&self, active: &[usize], name: &str) -> Vec<usize> {
+        let mut next = Vec::new();
+        for &s in active {
+            match self.segments.get(s) {
+                Some(Segment::Recursive) => {
+                    // `**` consumes this component and stays active.
+                    next.push(s);
+                }
+                Some(Segment::Pattern(tokens)) => {
+                    if component_matches(tokens, name) {
+                        next.push(s + 1);
+                    }
+                }
+                None => {} // terminal state: nothing deeper can match.
+            }
+        }
+        self.closure(next)
+    }
+
+    /// Whether the active set contains the terminal (fully-consumed) state.
+    fn is_terminal(&self, active: &[usize]) -> bool {
+        active.contains(&self.segments.len())
+    }
+
+    /// Whether any active state could still match something deeper, i.e. it is worth descending.
+    fn can_descend(&self, active: &[usize]) -> bool {
+        active.iter().any(|&s| s < self.segments.len())
+    }
+}
+
+fn compile_component(component: &str) -> Result<Vec<Token>, PatternError> {
+    let mut tokens = Vec::new();
+    let mut chars = component.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '?' => tokens.push(Token::One),
+            '*' => tokens.push(Token::Any),
+            '[' => {
+                let mut items = Vec::new();
+                loop {
+                    let start = chars.next().ok_or(PatternError::UnterminatedClass)?;
+                    if start == ']' {
+                        break;
+                    }
+                    if chars.peek() == Some(&'-') {
+                        chars.next(); // consume '-'
+                        let end = chars.next().ok_or(PatternError::UnterminatedClass)?;
+                        if end == ']' {
+                            // A trailing '-' is a literal, e.g. `[a-]`.
+                            items.push(ClassItem::Char(start.to_ascii_lowercase()));
+                            items.push(ClassItem::Char('-'));
+                            break;
+                        }
+                        items.push(ClassItem::Range(start.to_ascii_lowercase(), end.to_ascii_lowercase()));
+                    } else {
+                        items.push(ClassItem::Char(start.to_ascii_lowercase()));
+                    }
+                }
+                if items.is_empty() {
+                    return Err(PatternError::EmptyClass);
+                }
+                tokens.push(Token::Class(items));
+            }
+            other => tokens.push(Token::Literal(other.to_ascii_lowercase())),
+        }
+    }
+    Ok(tokens)
+}
+
+/// ASCII-case-insensitive match of a single compiled component against a name, with `*` backtracking.
+fn component_matches(tokens: &[Token], name: &str) -> bool {
+    let chars: Vec<char> = name.chars().map(|c| c.to_ascii_lowercase()).collect();
+    matches_from(tokens, &chars)
+}
+
+fn matches_from(tokens: &[Token], chars: &[char]) -> bool {
+    match tokens.split_first() {
+        None => chars.is_empty(),
+        Some((Token::Any, rest)) => {
+            // `*` matches any prefix (including empty); try every split point.
+            for i in 0..=chars.len() {
+                if matches_from(rest, &chars[i..]) {
+                    return true;
+                }
+            }
+            false
+        }
+        Some((token, rest)) => {
+            let Some((&c, tail)) = chars.split_first() else {
+                return false;
+            };
+            let ok = match token {
+                Token::One => true,
+                Token::Literal(l) => *l == c,
+                Token::Class(items) => items.iter().any(|item| match item {
+                    ClassItem::Char(x) => *x == c,
+                    ClassItem::Range(lo, hi) => *lo <= c && c <= *hi,
+                }),
+                Token::Any => unreachable!(),
+            };
+            ok && matches_from(rest, tail)
+        }
+    }
+}
+
+/// One level of the traversal stack: an open folder's children and the NFA states that apply to
+/// them.
+struct Frame {
+    children: ObjectIterator,
+    active: Vec<usize>,
+}
+
+/// Iterator returned by [`Matcher::matches_in`] and [`Object::object_by_pattern`].
+///
+/// It is generic over how the compiled matcher is held — by reference for
+/// [`matches_in`](Matcher::matches_in), or owned for [`Object::object_by_pattern`](super::Object::object_by_pattern).
+pub struct PatternMatches<M: Borrow<Matcher>> {
+    matcher: M,
+    stack: Vec<Frame>,
+    pending_error: Option<windows::core::Error>,
+}
+
+impl<M: Borrow<Matcher>> PatternMatches<M> {
+    fn start(matcher: M, root: &Object) -> Self {
+        let start = matcher.borrow().closure(vec![0]);
+        let mut matches = Self {
+            matcher,
+            stack: Vec::new(),
+            pending_error: None,
+        };
+        match root.children() {
+            Ok(children) => matches.stack.push(Frame { children, active: start }),
+            Err(e) => matches.pending_error = Some(e),
+        }
+        matches
+    }
+}
+
+impl<M: Borrow<Matcher>> Iterator for PatternMatches<M> {
+    type Item = crate::WindowsResult<Object>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(e) = self.pending_error.take() {
+            return Some(Err(e));
+        }
+
+        let matcher = self.matcher.borrow();
+        loop {
+            let frame = self.stack.last_mut()?;
+
+            let object = match frame.children.next() {
+                Some(object) => object,
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+
+            let name = object.name().to_string_lossy();
+            let active = matcher.advance(&frame.active, &name);
+            if active.is_empty() {
+                continue;
+            }
+
+            let is_folder = object.object_type() == ObjectType::Folder;
+
+            if is_folder && matcher.can_descend(&active) {
+                match object.children() {
+                    Ok(children) => self.stack.push(Frame { children, active: active.clone() }),
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+
+            if matcher.is_terminal(&active) && !is_folder {
+                return Some(Ok(object));
+            }
+        }
+    }
+}
@@ -19,6 +19,21 @@ pub use object_type::ObjectType;
 mod object_iterator;
 pub use object_iterator::ObjectIterator;
 
+mod object_properties;
+pub use object_properties::{ObjectProperties, PropertySet};
+
+mod walk;
+pub use walk::{Walk, WalkIter, WalkEntry, WalkError};
+
+mod matcher;
+pub use matcher::{Matcher, PatternError, PatternMatches};
+
+mod par_walk;
+pub use par_walk::{ParWalk, ParWalkIter};
+
+mod recursive;
+pub use recursive::CopyReport;
+
 
 #[derive(Debug, Clone)]
 pub struct Object {
@@ -28,11 +43,19 @@ pub struct Object {
     /// The object display name (e.g. "PIC_001.jpg")
     name: U16CString,
     ty: ObjectType,
+    /// Properties pre-loaded during enumeration, if any, so that accessors avoid extra COM calls.
+    properties: Option<ObjectProperties>,
 }
 
 impl Object {
     pub fn new(device_content: Content, id: U16CString, name: U16CString, ty: ObjectType) -> Self {
-        Self { device_content, id, name, ty }
+        Self { device_content, id, name, ty, properties: None }
+    }
+
+    /// Attach a batch of properties fetched during enumeration, so that later getters hit the cache.
+    pub(crate) fn with_cached_properties(mut self, properties: ObjectProperties) -> Self {
+        self.properties = Some(properties);
+        self
     }
 
     pub(crate) fn device_content(&self) -> &Content {
@@ -44,16 +67,28 @@ impl Object {
     }
 
     pub fn name(&self) -> &U16CStr {
-        // TODO: lazy evaluation (of all properties at once to save calls to properties.GetValues) (depends on how much iterating/filtering by folder is baked-in)?
         &self.name
     }
 
     pub fn object_type(&self) -> ObjectType {
-        // TODO: lazy evaluation?
         self.ty
     }
 
+    /// Fetch a batch of WPD properties for this object in a single COM call.
+    ///
+    /// When the object was produced by an [`ObjectIterator`] with a pre-loaded
+    /// [`PropertySet`], the cached batch is returned and no COM call is made.
+    pub fn properties(&self) -> crate::WindowsResult<ObjectProperties> {
+        if let Some(properties) = &self.properties {
+            return Ok(properties.clone());
+        }
+        ObjectProperties::fetch(&self.device_content, &self.id, PropertySet::Full)
+    }
+
     pub fn parent_id(&self) -> crate::WindowsResult<U16CString> {
+        if let Some(parent_id) = self.properties.as_ref().and_then(|p| p.parent_id()) {
+            return Ok(parent_id.to_ucstring());
+        }
         let parent_id_props = self.device_content.get_object_properties(&self.id, &[WPD_OBJECT_PARENT_ID])?;
         let parent_id_pwstr = unsafe{ parent_id_props.GetStringValue(&WPD_OBJECT_PARENT_ID as *const _) }?;
         Ok(U16CString::from_vec_truncate(unsafe{ parent_id_pwstr.as_wide() }))
@@ -61,6 +96,14 @@ impl Object {
 
     /// Returns an iterator to list every children of the current object (including sub-folders)
     pub fn children(&self) -> crate::WindowsResult<ObjectIterator> {
+        self.children_with(PropertySet::Minimal)
+    }
+
+    /// Like [`children`](Self::children), but pre-loading `set` for every enumerated object so that
+    /// later `name()`/`object_type()` calls, and `parent_id()` calls when `set` includes the parent
+    /// id, are cache hits rather than fresh COM round-trips. [`walk`](Self::walk) uses this to avoid
+    /// a `parent_id()` round-trip per entry.
+    pub fn children_with(&self, set: PropertySet) -> crate::WindowsResult<ObjectIterator> {
         let com_iter = unsafe{
             self.device_content.com_object().EnumObjects(
                 0,
@@ -69,7 +112,7 @@ impl Object {
             )
         }?;
 
-        Ok(ObjectIterator::new(&self.device_content, com_iter))
+        Ok(ObjectIterator::new(&self.device_content, com_iter).with_properties(set))
     }
 
     /// Returns an iterator that only lists folders within this object
@@ -77,6 +120,42 @@ impl Object {
         self.children().map(|children| children.filter(|obj| obj.object_type() == ObjectType::Folder))
     }
 
+    /// Recursively enumerate every descendant of this object, depth-first.
+    ///
+    /// Returns a [`Walk`] builder (modeled on the `walkdir` crate) that you configure with
+    /// depth bounds, a [`filter_entry`](Walk::filter_entry) predicate and
+    /// [`contents_first`](Walk::contents_first) ordering before iterating.<br/>
+    /// Unlike [`object_by_path`](Self::object_by_path), the walk keeps an explicit stack of open
+    /// folder iterators, so it visits deep trees without recursing and without re-resolving each
+    /// level by name.
+    pub fn walk(&self) -> Walk<'_> {
+        Walk::new(self)
+    }
+
+    /// Enumerate every descendant in parallel, trading ordering for throughput.
+    ///
+    /// Returns a [`ParWalk`] builder that fans the traversal across a pool of worker threads, each
+    /// with its own COM initialization and device handles, yielding `(depth, object)` pairs as they are
+    /// discovered.<br/>
+    /// Because MTP forbids caching for race-safety, the results are a point-in-time snapshot and
+    /// their order is unspecified. Use [`walk`](Self::walk) when deterministic, depth-first order
+    /// matters.
+    pub fn par_walk(&self) -> ParWalk<'_> {
+        ParWalk::new(self)
+    }
+
+    /// Find every descendant file whose relative path matches a glob `pattern`.
+    ///
+    /// The pattern understands `?`, `*`, `**` and `[..]` character classes and is matched
+    /// ignoring ASCII case (other characters must match exactly), so `DCIM/**/*.jpg` yields every JPEG anywhere under `DCIM`.<br/>
+    /// This compiles a fresh [`Matcher`] each call; build one with [`Matcher::new`] and reuse it
+    /// via [`Matcher::matches_in`] when running the same pattern against several devices.
+    pub fn object_by_pattern(&self, pattern: &str) -> Result<PatternMatches<Matcher>, PatternError> {
+        // The returned iterator owns its compiled matcher, so it can outlive this call.
+        let matcher = Matcher::new(pattern)?;
+        Ok(matcher.into_matches_in(self))
+    }
+
     /// Retrieve an item by its path
     ///
     /// This function looks for a sub-item with the right name, then iteratively does so for the matching child.<br/>
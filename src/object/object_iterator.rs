@@ -0,0 +1,128 @@
+//! Iterator over the children of an [`Object`], backed by WPD's `IEnumPortableDeviceObjectIDs`.
+//!
+//! Object IDs are pulled from the device in batches; for each one the iterator fetches a
+//! [`PropertySet`] in a single `GetValues` call and stores it on the yielded [`Object`], so that
+//! subsequent `name()`/`object_type()` calls are cache hits rather than fresh COM round-trips, as
+//! are `parent_id()` calls when the set includes the parent id ([`PropertySet::Full`]; the default
+//! [`PropertySet::Minimal`] does not).
+//!
+//! Note that this collapses the *several* getters previously issued per object (name, then type,
+//! then parent id, …) into *one* call per object — it does not batch a whole folder into a single
+//! call. True per-folder batching would require `IPortableDevicePropertiesBulk`, which is not yet
+//! wired up here; pre-loading is therefore one round-trip per object, not one per folder.
+
+use windows::core::PWSTR;
+use windows::Win32::Devices::PortableDevices::IEnumPortableDeviceObjectIDs;
+use widestring::U16CString;
+
+use crate::device::Content;
+use super::{Object, ObjectType, ObjectProperties, PropertySet};
+
+/// Number of object IDs pulled from the device per `Next` call.
+const BATCH_SIZE: u32 = 32;
+
+/// Iterates the children of a folder, yielding a fully-built [`Object`] for each.
+pub struct ObjectIterator {
+    device_content: Content,
+    com_iterator: IEnumPortableDeviceObjectIDs,
+    property_set: PropertySet,
+    /// IDs already pulled from the device but not yet yielded.
+    batch: std::vec::IntoIter<U16CString>,
+    /// Set once the device reports no further IDs.
+    exhausted: bool,
+}
+
+impl ObjectIterator {
+    pub(crate) fn new(device_content: &Content, com_iterator: IEnumPortableDeviceObjectIDs) -> Self {
+        Self {
+            device_content: device_content.clone(),
+            com_iterator,
+            property_set: PropertySet::Minimal,
+            batch: Vec::new().into_iter(),
+            exhausted: false,
+        }
+    }
+
+    /// Pre-load `set` for every enumerated object.
+    ///
+    /// The extra keys are fetched in the same `GetValues` call used to resolve each object's name
+    /// and type, so requesting a larger set is free beyond the memory it occupies: it collapses
+    /// what would otherwise be several per-object getters into a single round-trip. It does not,
+    /// however, batch a whole folder into one call (that would need `IPortableDevicePropertiesBulk`);
+    /// the cost is still one `GetValues` per object. Defaults to [`PropertySet::Minimal`].
+    pub fn with_properties(mut self, set: PropertySet) -> Self {
+        self.property_set = set;
+        self
+    }
+
+    /// Pull the next batch of object IDs from the device, returning `false` when the enumerator is
+    /// drained.
+    fn refill(&mut self) -> bool {
+        if self.exhausted {
+            return false;
+        }
+
+        let mut raw = vec![PWSTR::null(); BATCH_SIZE as usize];
+        let mut fetched: u32 = 0;
+        let hr = unsafe {
+            self.com_iterator.Next(&mut raw, &mut fetched as *mut u32)
+        };
+        // `Next` returns S_FALSE once fewer than requested IDs remain; treat any non-error as done
+        // when nothing more was produced.
+        if hr.is_err() || fetched == 0 {
+            self.exhausted = true;
+        }
+
+        let ids: Vec<U16CString> = raw
+            .into_iter()
+            .take(fetched as usize)
+            .filter(|pwstr| !pwstr.is_null())
+            .map(|pwstr| unsafe {
+                let id = U16CString::from_vec_truncate(pwstr.as_wide());
+                // The enumerator hands ownership of each string to us; release it.
+                windows::Win32::System::Com::CoTaskMemFree(Some(pwstr.as_ptr() as *const _));
+                id
+            })
+            .collect();
+
+        let any = !ids.is_empty();
+        self.batch = ids.into_iter();
+        any
+    }
+
+    /// Build the [`Object`] for `id`, pre-loading and caching its property set.
+    fn build(&self, id: U16CString) -> Object {
+        let properties = ObjectProperties::fetch(&self.device_content, &id, self.property_set).ok();
+
+        let name = properties
+            .as_ref()
+            .and_then(|p| p.name())
+            .map(U16CString::from)
+            .unwrap_or_default();
+        let ty = properties
+            .as_ref()
+            .and_then(|p| p.object_type())
+            .unwrap_or(ObjectType::File);
+
+        let object = Object::new(self.device_content.clone(), id, name, ty);
+        match properties {
+            Some(properties) => object.with_cached_properties(properties),
+            None => object,
+        }
+    }
+}
+
+impl Iterator for ObjectIterator {
+    type Item = Object;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(id) = self.batch.next() {
+                return Some(self.build(id));
+            }
+            if !self.refill() {
+                return None;
+            }
+        }
+    }
+}
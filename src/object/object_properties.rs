@@ -0,0 +1,158 @@
+//! A batch of WPD properties fetched for a single [`Object`] in one COM round-trip.
+//!
+//! Every `parent_id()` or `name()` used to cost a separate `IPortableDeviceProperties::GetValues`
+//! call, which dominates the cost of walking a large card. [`ObjectProperties`] requests a whole
+//! set of keys at once and exposes them through typed accessors; [`ObjectIterator`](super::ObjectIterator)
+//! can pre-load it for every enumerated object so later getters are cache hits.
+
+use std::time::{Duration, SystemTime};
+
+use windows::core::GUID;
+use windows::Win32::Devices::PortableDevices::{
+    WPD_OBJECT_PARENT_ID, WPD_OBJECT_NAME, WPD_OBJECT_SIZE, WPD_OBJECT_DATE_MODIFIED,
+    WPD_OBJECT_CONTENT_TYPE, WPD_OBJECT_ORIGINAL_FILE_NAME, WPD_CONTENT_TYPE_FOLDER,
+};
+use windows::Win32::Foundation::PROPERTYKEY;
+use windows::Win32::System::Variant::{PROPVARIANT, VT_DATE};
+use widestring::{U16CString, U16CStr};
+
+use crate::device::Content;
+use super::ObjectType;
+
+/// Which batch of WPD keys an [`ObjectIterator`](super::ObjectIterator) pre-loads per object.
+///
+/// Larger sets trade memory for fewer COM calls during a walk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertySet {
+    /// Only the keys needed to build an [`Object`](super::Object): name and content type.
+    Minimal,
+    /// Everything [`ObjectProperties`] can expose: name, type, parent id, size, modified date,
+    /// content type and original file name.
+    Full,
+}
+
+impl PropertySet {
+    /// The WPD keys to request for this set.
+    pub(crate) fn keys(self) -> &'static [PROPERTYKEY] {
+        match self {
+            PropertySet::Minimal => &[WPD_OBJECT_NAME, WPD_OBJECT_CONTENT_TYPE],
+            PropertySet::Full => &[
+                WPD_OBJECT_NAME,
+                WPD_OBJECT_CONTENT_TYPE,
+                WPD_OBJECT_PARENT_ID,
+                WPD_OBJECT_SIZE,
+                WPD_OBJECT_DATE_MODIFIED,
+                WPD_OBJECT_ORIGINAL_FILE_NAME,
+            ],
+        }
+    }
+}
+
+/// A snapshot of the WPD properties of one object.
+///
+/// Fields are `Option` because a device is free to omit any key it does not support; the accessors
+/// fall back to a sensible default where the request documents one.
+#[derive(Debug, Clone, Default)]
+pub struct ObjectProperties {
+    name: Option<U16CString>,
+    content_type: Option<GUID>,
+    parent_id: Option<U16CString>,
+    size: Option<u64>,
+    modified: Option<SystemTime>,
+    original_file_name: Option<U16CString>,
+}
+
+impl ObjectProperties {
+    /// Fetch `set` for `object_id` in a single `GetValues` call.
+    pub(crate) fn fetch(content: &Content, object_id: &U16CStr, set: PropertySet) -> crate::WindowsResult<Self> {
+        let values = content.get_object_properties(object_id, set.keys())?;
+        Ok(Self::from_values(&values))
+    }
+
+    /// Parse an `IPortableDeviceValues`, keeping only the keys that are present.
+    pub(crate) fn from_values(values: &windows::Win32::Devices::PortableDevices::IPortableDeviceValues) -> Self {
+        let string_of = |key: &PROPERTYKEY| unsafe {
+            values.GetStringValue(key as *const _).ok()
+                .map(|pwstr| U16CString::from_vec_truncate(pwstr.as_wide()))
+        };
+
+        let content_type = unsafe { values.GetGuidValue(&WPD_OBJECT_CONTENT_TYPE as *const _).ok() };
+        let size = unsafe { values.GetUnsignedLargeIntegerValue(&WPD_OBJECT_SIZE as *const _).ok() };
+        // `WPD_OBJECT_DATE_MODIFIED` is a `VT_DATE` (8-byte OLE automation double), not a float, so
+        // we must read the raw PROPVARIANT rather than `GetFloatValue`.
+        let modified = unsafe { values.GetValue(&WPD_OBJECT_DATE_MODIFIED as *const _).ok() }
+            .and_then(|variant| ole_date_from_propvariant(&variant))
+            .and_then(ole_date_to_system_time);
+
+        Self {
+            name: string_of(&WPD_OBJECT_NAME),
+            content_type,
+            parent_id: string_of(&WPD_OBJECT_PARENT_ID),
+            size,
+            modified,
+            original_file_name: string_of(&WPD_OBJECT_ORIGINAL_FILE_NAME),
+        }
+    }
+
+    /// The object display name (`WPD_OBJECT_NAME`), if present.
+    pub fn name(&self) -> Option<&U16CStr> {
+        self.name.as_deref()
+    }
+
+    /// The derived [`ObjectType`], inferred from the content type GUID.
+    pub fn object_type(&self) -> Option<ObjectType> {
+        self.content_type.map(|guid| {
+            if guid == WPD_CONTENT_TYPE_FOLDER {
+                ObjectType::Folder
+            } else {
+                ObjectType::File
+            }
+        })
+    }
+
+    /// The id of the parent object (`WPD_OBJECT_PARENT_ID`), if present.
+    pub fn parent_id(&self) -> Option<&U16CStr> {
+        self.parent_id.as_deref()
+    }
+
+    /// The object size in bytes, or `0` if the device did not report one.
+    pub fn size(&self) -> u64 {
+        self.size.unwrap_or(0)
+    }
+
+    /// The last-modified time, if the device reported a valid date.
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.modified
+    }
+
+    /// The original file name (`WPD_OBJECT_ORIGINAL_FILE_NAME`), if present.
+    pub fn original_file_name(&self) -> Option<&U16CStr> {
+        self.original_file_name.as_deref()
+    }
+}
+
+/// Extract the `VT_DATE` double from a PROPVARIANT, returning `None` for any other variant type.
+fn ole_date_from_propvariant(variant: &PROPVARIANT) -> Option<f64> {
+    unsafe {
+        if variant.Anonymous.Anonymous.vt == VT_DATE {
+            Some(variant.Anonymous.Anonymous.Anonymous.date)
+        } else {
+            None
+        }
+    }
+}
+
+/// Convert an OLE automation date (days since 1899-12-30, as used by `VT_DATE`) to a `SystemTime`.
+///
+/// Returns `None` for dates before the Unix epoch, which a valid modified-time should never be.
+fn ole_date_to_system_time(ole_date: f64) -> Option<SystemTime> {
+    // The Unix epoch (1970-01-01) is 25569 days after the OLE epoch (1899-12-30).
+    const OLE_EPOCH_TO_UNIX_DAYS: f64 = 25569.0;
+    const SECONDS_PER_DAY: f64 = 86_400.0;
+
+    let unix_seconds = (ole_date - OLE_EPOCH_TO_UNIX_DAYS) * SECONDS_PER_DAY;
+    if unix_seconds < 0.0 {
+        return None;
+    }
+    Some(SystemTime::UNIX_EPOCH + Duration::from_secs_f64(unix_seconds))
+}
@@ -0,0 +1,195 @@
+//! Parallel, throughput-oriented subtree enumeration.
+//!
+//! The serial [`walk`](super::Object::walk) is latency-bound: each folder's `EnumObjects` and
+//! property fetch must complete before the next folder starts. [`Object::par_walk`](super::Object::par_walk)
+//! instead fans the traversal across a pool of worker threads that share a work queue of folder ids.
+//!
+//! Because COM objects are thread-affine, every worker calls `CoInitializeEx` and obtains its own
+//! [`Content`] (and thus its own enumerator) from the device rather than sharing the caller's.
+//! MTP forbids caching for race-safety, so results are a point-in-time snapshot and their order is
+//! unspecified.
+
+use std::sync::{Arc, Condvar, Mutex};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread::JoinHandle;
+
+use windows::Win32::System::Com::{CoInitializeEx, CoUninitialize, COINIT_MULTITHREADED};
+use widestring::U16CString;
+
+use crate::device::DeviceInfo;
+use super::{Object, ObjectType};
+
+/// Default number of worker threads when the caller does not cap it.
+const DEFAULT_WORKERS: usize = 4;
+/// Bound on the result channel, to apply backpressure on fast producers.
+const CHANNEL_BOUND: usize = 256;
+
+/// Builder for a parallel subtree walk. Obtain one from [`Object::par_walk`](super::Object::par_walk).
+pub struct ParWalk<'a> {
+    root: &'a Object,
+    workers: usize,
+}
+
+impl<'a> ParWalk<'a> {
+    pub(super) fn new(root: &'a Object) -> Self {
+        Self { root, workers: DEFAULT_WORKERS }
+    }
+
+    /// Cap the number of worker threads (clamped to at least 1).
+    pub fn workers(mut self, count: usize) -> Self {
+        self.workers = count.max(1);
+        self
+    }
+}
+
+impl<'a> IntoIterator for ParWalk<'a> {
+    type Item = crate::WindowsResult<(usize, Object)>;
+    type IntoIter = ParWalkIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        // COM interfaces are apartment-affine, so we cannot share the caller's device handle with
+        // the workers. Instead we carry the `DeviceInfo` (plain, `Send` data) to each worker and let
+        // it open its own `IPortableDevice` on its own thread.
+        let device_info = self.root.device_content().device().info().clone();
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                stack: vec![(self.root.id().to_ucstring(), 0)],
+                pending: 1,
+                stop: false,
+            }),
+            available: Condvar::new(),
+        });
+
+        let (sender, receiver) = mpsc::sync_channel(CHANNEL_BOUND);
+
+        let handles = (0..self.workers)
+            .map(|_| {
+                let shared = Arc::clone(&shared);
+                let device_info = device_info.clone();
+                let sender = sender.clone();
+                std::thread::spawn(move || worker(shared, device_info, sender))
+            })
+            .collect();
+
+        ParWalkIter { receiver, handles, shared }
+    }
+}
+
+/// Iterator over `(depth, object)` pairs discovered by the worker pool.
+pub struct ParWalkIter {
+    receiver: Receiver<crate::WindowsResult<(usize, Object)>>,
+    handles: Vec<JoinHandle<()>>,
+    shared: Arc<Shared>,
+}
+
+impl Iterator for ParWalkIter {
+    type Item = crate::WindowsResult<(usize, Object)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Returns `None` once every worker has exited and dropped its sender.
+        self.receiver.recv().ok()
+    }
+}
+
+impl Drop for ParWalkIter {
+    fn drop(&mut self) {
+        // Tell the workers to stop, then drain the channel so any blocked on a full send can make
+        // progress and exit, before joining them.
+        {
+            let mut state = self.shared.state.lock().unwrap();
+            state.stop = true;
+        }
+        self.shared.available.notify_all();
+        while self.receiver.recv().is_ok() {}
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Work shared between the pool: the folder queue plus the count of folders still in flight.
+struct Shared {
+    state: Mutex<State>,
+    available: Condvar,
+}
+
+struct State {
+    /// Folders discovered but not yet enumerated, as `(id, depth)`.
+    stack: Vec<(U16CString, usize)>,
+    /// Folders queued or currently being enumerated; the walk is done when this reaches zero.
+    pending: usize,
+    stop: bool,
+}
+
+/// The body of each worker thread.
+fn worker(shared: Arc<Shared>, device_info: DeviceInfo, sender: SyncSender<crate::WindowsResult<(usize, Object)>>) {
+    // Each worker joins the process's multithreaded apartment and opens its own device handle
+    // there, rather than sharing one opened on the caller's thread.
+    let _ = unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) };
+    let content = match device_info.open().and_then(|device| device.content()) {
+        Ok(content) => content,
+        Err(e) => {
+            let _ = sender.send(Err(e));
+            unsafe { CoUninitialize() };
+            return;
+        }
+    };
+
+    loop {
+        let (folder_id, depth) = {
+            let mut state = shared.state.lock().unwrap();
+            loop {
+                if state.stop {
+                    return_uninit();
+                    return;
+                }
+                if let Some(item) = state.stack.pop() {
+                    break item;
+                }
+                if state.pending == 0 {
+                    // Nothing left anywhere: wake the others so they can exit too.
+                    state.stop = true;
+                    shared.available.notify_all();
+                    return_uninit();
+                    return;
+                }
+                state = shared.available.wait(state).unwrap();
+            }
+        };
+
+        let child_depth = depth + 1;
+        match content.object_by_id(folder_id.clone()).and_then(|folder| folder.children()) {
+            Ok(children) => {
+                for child in children {
+                    if child.object_type() == ObjectType::Folder {
+                        let mut state = shared.state.lock().unwrap();
+                        state.stack.push((child.id().to_ucstring(), child_depth));
+                        state.pending += 1;
+                        shared.available.notify_one();
+                    }
+                    if sender.send(Ok((child_depth, child))).is_err() {
+                        // Consumer hung up.
+                        return_uninit();
+                        return;
+                    }
+                }
+            }
+            Err(e) => {
+                let _ = sender.send(Err(e));
+            }
+        }
+
+        // This folder is fully enumerated.
+        let mut state = shared.state.lock().unwrap();
+        state.pending -= 1;
+        if state.pending == 0 {
+            state.stop = true;
+            shared.available.notify_all();
+        }
+    }
+}
+
+/// Leave the worker's COM apartment. Kept as a helper so every early return goes through it.
+fn return_uninit() {
+    unsafe { CoUninitialize() };
+}
@@ -0,0 +1,166 @@
+//! Bulk copy and delete helpers, driven by the depth-first [`walk`](super::Object::walk).
+//!
+//! These build on the traversal subsystem instead of hand-rolling recursion:
+//! [`remove_recursive`](super::Object::remove_recursive) walks [`contents_first`](super::Walk::contents_first)
+//! so children are deleted before their (non-empty-folder-refusing) parent, and
+//! [`copy_subtree_to`](super::Object::copy_subtree_to) walks pre-order so each destination folder
+//! exists before anything is created inside it. Both collect per-object failures into a report
+//! rather than aborting on the first error, and both accept the same
+//! [`filter_entry`](super::Walk::filter_entry) predicate as the walker.
+
+use std::collections::HashMap;
+
+use windows::Win32::Foundation::{E_ABORT, E_FAIL};
+
+use super::{Object, ObjectType, ObjectId, WalkEntry};
+
+/// Outcome of a [`copy_subtree_to`](Object::copy_subtree_to): which source objects were recreated
+/// under the destination and which failed.
+#[derive(Debug, Default)]
+pub struct CopyReport {
+    /// Ids of source objects copied successfully.
+    pub succeeded: Vec<ObjectId>,
+    /// Source objects that could not be copied, with the error encountered.
+    pub failed: Vec<(ObjectId, windows::core::Error)>,
+}
+
+impl Object {
+    /// Recursively delete this object and everything beneath it.
+    ///
+    /// Deletes every descendant (via [`remove_recursive_filtered`](Self::remove_recursive_filtered))
+    /// and then the now-empty object itself.
+    pub fn remove_recursive(&self) -> Vec<(ObjectId, windows::core::Error)> {
+        let mut failures = self.remove_recursive_filtered(|_| true);
+        // The filtered walk only removes descendants; the whole subtree was accepted, so the root
+        // is now empty and can be deleted too.
+        if let Err(e) = self.device_content().delete(self.id()) {
+            failures.push((ObjectId::new(self.id().to_ucstring()), e));
+        }
+        failures
+    }
+
+    /// Recursively delete the descendants accepted by `filter`.
+    ///
+    /// Uses [`contents_first`](super::Walk::contents_first) ordering so a folder is only deleted
+    /// after its contents, as MTP refuses to delete a non-empty folder. Per-object delete failures
+    /// are collected and returned rather than aborting the walk.
+    ///
+    /// This never deletes the object itself: a partial filter may leave it non-empty, so removing
+    /// the root is left to [`remove_recursive`](Self::remove_recursive), which accepts the whole
+    /// subtree.
+    pub fn remove_recursive_filtered<P>(&self, filter: P) -> Vec<(ObjectId, windows::core::Error)>
+    where
+        P: FnMut(&Object) -> bool + 'static,
+    {
+        let content = self.device_content();
+        let mut failures = Vec::new();
+
+        for entry in self.walk().contents_first(true).filter_entry(filter) {
+            match entry {
+                Ok(WalkEntry { object, .. }) => {
+                    if let Err(e) = content.delete(object.id()) {
+                        failures.push((ObjectId::new(object.id().to_ucstring()), e));
+                    }
+                }
+                Err(e) => {
+                    // Attribute the enumeration failure to the folder that actually failed.
+                    let id = e.object_id
+                        .map(ObjectId::new)
+                        .unwrap_or_else(|| ObjectId::new(self.id().to_ucstring()));
+                    failures.push((id, e.source));
+                }
+            }
+        }
+
+        failures
+    }
+
+    /// Recursively copy this subtree under `dest`.
+    ///
+    /// Equivalent to [`copy_subtree_to_filtered`](Self::copy_subtree_to_filtered) with a predicate
+    /// that accepts everything.
+    pub fn copy_subtree_to(&self, dest: &Object) -> CopyReport {
+        self.copy_subtree_to_filtered(dest, |_| true)
+    }
+
+    /// Recursively copy the descendants accepted by `filter` under `dest`.
+    ///
+    /// The folder hierarchy is recreated as the walk descends (pre-order, so each parent exists
+    /// before its children), and leaf objects have their contents streamed into the matching
+    /// destination folder. Returns a [`CopyReport`] of succeeded and failed source ids.
+    pub fn copy_subtree_to_filtered<P>(&self, dest: &Object, filter: P) -> CopyReport
+    where
+        P: FnMut(&Object) -> bool + 'static,
+    {
+        let mut report = CopyReport::default();
+
+        // Maps a source object id to the destination folder that mirrors it. Seeded with the root,
+        // whose mirror is `dest` itself.
+        let mut mirror: HashMap<_, Object> = HashMap::new();
+        mirror.insert(self.id().to_ucstring(), dest.clone());
+
+        for entry in self.walk().filter_entry(filter) {
+            let object = match entry {
+                Ok(WalkEntry { object, .. }) => object,
+                Err(e) => {
+                    // A folder we could not enumerate: its whole subtree is skipped, so record the
+                    // failure rather than silently reporting a complete copy.
+                    let id = e.object_id
+                        .map(ObjectId::new)
+                        .unwrap_or_else(|| ObjectId::new(self.id().to_ucstring()));
+                    report.failed.push((id, e.source));
+                    continue;
+                }
+            };
+
+            let source_id = ObjectId::new(object.id().to_ucstring());
+
+            // Resolve the destination parent; if its creation failed earlier, so does this one.
+            let parent_id = match object.parent_id() {
+                Ok(parent_id) => parent_id,
+                Err(e) => {
+                    report.failed.push((source_id, e));
+                    continue;
+                }
+            };
+            let Some(dest_parent) = mirror.get(&parent_id).cloned() else {
+                report.failed.push((source_id, windows::core::Error::from(E_ABORT)));
+                continue;
+            };
+
+            if object.object_type() == ObjectType::Folder {
+                match dest_parent.create_folder(object.name()) {
+                    Ok(new_folder) => {
+                        mirror.insert(object.id().to_ucstring(), new_folder);
+                        report.succeeded.push(source_id);
+                    }
+                    Err(e) => report.failed.push((source_id, e)),
+                }
+            } else {
+                match copy_file(&object, &dest_parent) {
+                    Ok(()) => report.succeeded.push(source_id),
+                    Err(e) => report.failed.push((source_id, e)),
+                }
+            }
+        }
+
+        report
+    }
+}
+
+/// Stream a single leaf object's contents into `dest_parent`, preserving its name.
+///
+/// The source is read and the destination written through the content layer's stream wrappers, so
+/// the object is copied in fixed-size chunks rather than materialized in memory — multi-GB files do
+/// not blow up the heap.
+fn copy_file(source: &Object, dest_parent: &Object) -> crate::WindowsResult<()> {
+    let size = source.properties()?.size();
+    let mut reader = source.open_read()?;
+    let mut writer = dest_parent.create_object_writer(source.name(), size)?;
+
+    std::io::copy(&mut reader, &mut writer)
+        .map_err(|e| windows::core::Error::new(E_FAIL, e.to_string()))?;
+
+    // Flush and commit the WPD transfer; only then is the destination object complete.
+    writer.commit()
+}
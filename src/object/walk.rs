@@ -0,0 +1,248 @@
+//! Recursive, depth-first traversal of an [`Object`] subtree.
+//!
+//! Modeled on the `walkdir` crate: [`Object::walk`](super::Object::walk) returns a [`Walk`]
+//! builder that you configure and then iterate. Each yielded [`WalkEntry`] carries the `depth` at
+//! which it was found, so callers can reconstruct the hierarchy without a second lookup.
+
+use widestring::U16CString;
+
+use super::{Object, ObjectType, ObjectIterator, PropertySet};
+
+/// A single entry produced by a [`Walk`]: an [`Object`] together with its depth below the root.
+///
+/// The root object is depth 0 (and never yielded); its direct children are depth 1, and so on.
+#[derive(Debug, Clone)]
+pub struct WalkEntry {
+    /// Depth of `object` below the walked root.
+    pub depth: usize,
+    /// The discovered object.
+    pub object: Object,
+}
+
+/// An error encountered mid-walk, i.e. a folder whose contents could not be enumerated.
+///
+/// It carries the id of the offending folder (when known) so callers can attribute the failure,
+/// rather than a bare COM error with no context.
+#[derive(Debug)]
+pub struct WalkError {
+    /// Id of the folder whose `EnumObjects` failed, when known.
+    pub object_id: Option<U16CString>,
+    /// The underlying COM error.
+    pub source: windows::core::Error,
+}
+
+impl std::fmt::Display for WalkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.object_id {
+            Some(id) => write!(f, "failed to enumerate folder {}: {}", id.to_string_lossy(), self.source),
+            None => write!(f, "failed to enumerate folder: {}", self.source),
+        }
+    }
+}
+
+impl std::error::Error for WalkError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// A configurable, depth-first walker over every descendant of an [`Object`].
+///
+/// Obtain one through [`Object::walk`](super::Object::walk). The root object itself is never
+/// yielded; iteration produces only its descendants as `Result<WalkEntry, WalkError>`, so that a
+/// failing per-folder `EnumObjects` surfaces as an `Err` item rather than aborting the whole walk.
+pub struct Walk<'a> {
+    root: &'a Object,
+    min_depth: usize,
+    max_depth: usize,
+    contents_first: bool,
+    filter_entry: Option<Box<dyn FnMut(&Object) -> bool>>,
+    property_set: PropertySet,
+}
+
+impl<'a> Walk<'a> {
+    pub(super) fn new(root: &'a Object) -> Self {
+        Self {
+            root,
+            min_depth: 0,
+            max_depth: usize::MAX,
+            contents_first: false,
+            filter_entry: None,
+            // Default to a set that includes the parent id, so that callers walking the tree (e.g.
+            // `copy_subtree_to`) hit the cache instead of issuing a `parent_id()` call per entry.
+            property_set: PropertySet::Full,
+        }
+    }
+
+    /// Choose which [`PropertySet`] is pre-loaded for every enumerated object.
+    ///
+    /// Defaults to [`PropertySet::Full`]; drop to [`PropertySet::Minimal`] to save memory when only
+    /// names and types are needed.
+    pub fn properties(mut self, set: PropertySet) -> Self {
+        self.property_set = set;
+        self
+    }
+
+    /// Do not yield entries shallower than `depth` (the root is depth 0, its children depth 1).
+    pub fn min_depth(mut self, depth: usize) -> Self {
+        self.min_depth = depth;
+        self
+    }
+
+    /// Do not descend into (nor yield) entries deeper than `depth`.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Yield the contents of a folder before the folder itself.
+    ///
+    /// Useful for recursive deletion, where children must be removed before their parent.
+    pub fn contents_first(mut self, yes: bool) -> Self {
+        self.contents_first = yes;
+        self
+    }
+
+    /// Skip entries (and, for folders, their whole subtree) for which `predicate` returns `false`.
+    pub fn filter_entry<P>(mut self, predicate: P) -> Self
+    where
+        P: FnMut(&Object) -> bool + 'static,
+    {
+        self.filter_entry = Some(Box::new(predicate));
+        self
+    }
+}
+
+impl<'a> IntoIterator for Walk<'a> {
+    type Item = Result<WalkEntry, WalkError>;
+    type IntoIter = WalkIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let mut iter = WalkIter {
+            stack: Vec::new(),
+            min_depth: self.min_depth,
+            max_depth: self.max_depth,
+            contents_first: self.contents_first,
+            filter_entry: self.filter_entry,
+            property_set: self.property_set,
+            pending_error: None,
+        };
+        // Seed the stack with the root's children at depth 1; the root itself is never yielded.
+        match self.root.children_with(self.property_set) {
+            Ok(children) => iter.stack.push(Frame { children, depth: 1, deferred: None }),
+            Err(source) => iter.pending_error = Some(WalkError {
+                object_id: Some(self.root.id().to_ucstring()),
+                source,
+            }),
+        }
+        iter
+    }
+}
+
+/// One level of the explicit traversal stack: an open folder's iterator plus the depth of the
+/// objects it yields. `deferred` holds the folder itself while `contents_first` is waiting to emit
+/// it after its contents have been drained.
+struct Frame {
+    children: ObjectIterator,
+    depth: usize,
+    deferred: Option<Object>,
+}
+
+/// Depth-first iterator produced by [`Walk::into_iter`].
+///
+/// The traversal is driven by an explicit stack of [`ObjectIterator`]s — one per open folder —
+/// rather than by native recursion, so arbitrarily deep trees do not overflow the stack.
+pub struct WalkIter {
+    stack: Vec<Frame>,
+    min_depth: usize,
+    max_depth: usize,
+    contents_first: bool,
+    filter_entry: Option<Box<dyn FnMut(&Object) -> bool>>,
+    property_set: PropertySet,
+    pending_error: Option<WalkError>,
+}
+
+impl WalkIter {
+    fn in_bounds(&self, depth: usize) -> bool {
+        depth >= self.min_depth && depth <= self.max_depth
+    }
+}
+
+impl Iterator for WalkIter {
+    type Item = Result<WalkEntry, WalkError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(e) = self.pending_error.take() {
+            return Some(Err(e));
+        }
+
+        loop {
+            let frame = self.stack.last_mut()?;
+
+            let object = match frame.children.next() {
+                Some(object) => object,
+                None => {
+                    // This folder is exhausted: pop it and, in `contents_first` mode, emit the
+                    // folder now that all of its contents have been yielded.
+                    let frame = self.stack.pop().expect("stack is non-empty");
+                    let parent_depth = frame.depth - 1;
+                    if let Some(object) = frame.deferred {
+                        if self.in_bounds(parent_depth) {
+                            return Some(Ok(WalkEntry { depth: parent_depth, object }));
+                        }
+                    }
+                    continue;
+                }
+            };
+
+            let depth = frame.depth;
+
+            if let Some(predicate) = self.filter_entry.as_mut() {
+                if !predicate(&object) {
+                    // Rejected: skip the entry and never descend into it.
+                    continue;
+                }
+            }
+
+            let descend = object.object_type() == ObjectType::Folder && depth < self.max_depth;
+
+            if self.contents_first {
+                if descend {
+                    match object.children_with(self.property_set) {
+                        Ok(children) => self.stack.push(Frame {
+                            children,
+                            depth: depth + 1,
+                            deferred: Some(object),
+                        }),
+                        Err(source) => return Some(Err(WalkError {
+                            object_id: Some(object.id().to_ucstring()),
+                            source,
+                        })),
+                    }
+                    // Hold the folder back until its subtree has been drained.
+                    continue;
+                }
+                if self.in_bounds(depth) {
+                    return Some(Ok(WalkEntry { depth, object }));
+                }
+            } else {
+                if descend {
+                    match object.children_with(self.property_set) {
+                        Ok(children) => self.stack.push(Frame {
+                            children,
+                            depth: depth + 1,
+                            deferred: None,
+                        }),
+                        Err(source) => return Some(Err(WalkError {
+                            object_id: Some(object.id().to_ucstring()),
+                            source,
+                        })),
+                    }
+                }
+                if self.in_bounds(depth) {
+                    return Some(Ok(WalkEntry { depth, object }));
+                }
+            }
+        }
+    }
+}